@@ -1,5 +1,7 @@
 pub mod rapl;
 
+use rapl::DOMAIN_COUNT;
+
 #[no_mangle]
 pub extern "C" fn start_rapl() -> i32 {
     rapl::start_rapl()
@@ -10,12 +12,29 @@ pub extern "C" fn stop_rapl() {
     rapl::stop_rapl();
 }
 
+/// Stop RAPL measurement and write the per-domain joules consumed (see
+/// `rapl::DOMAIN_NAMES` for the order) into `out`, a caller-owned buffer of
+/// at least `len` elements. Returns `0` on success, `-1` if `len` is too
+/// small to hold every domain.
+///
+/// # Safety
+/// `out` must point to at least `len` writable, properly aligned `f64`s.
+#[no_mangle]
+pub unsafe extern "C" fn stop_rapl_joules(out: *mut f64, len: usize) -> i32 {
+    if len < DOMAIN_COUNT {
+        return -1;
+    }
+    let deltas = rapl::stop_rapl().to_array();
+    std::ptr::copy_nonoverlapping(deltas.as_ptr(), out, DOMAIN_COUNT);
+    0
+}
+
 // JNI interface for Java
 #[cfg(target_os = "linux")]
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod jni {
     use jni::objects::{JClass};
-    use jni::sys::jint;
+    use jni::sys::{jdoubleArray, jint};
     use jni::JNIEnv;
 
     #[no_mangle]
@@ -33,4 +52,20 @@ pub mod jni {
     ) {
         crate::rapl::stop_rapl();
     }
+
+    /// Stop RAPL measurement and return the per-domain joules consumed (see
+    /// `rapl::DOMAIN_NAMES` for the order) as a Java `double[]`.
+    #[no_mangle]
+    pub extern "system" fn Java_RaplInterface_stopRaplJoules(
+        env: JNIEnv,
+        _class: JClass,
+    ) -> jdoubleArray {
+        let deltas = crate::rapl::stop_rapl().to_array();
+        let array = env
+            .new_double_array(deltas.len() as i32)
+            .expect("failed to allocate double[] for RAPL result");
+        env.set_double_array_region(array, 0, &deltas)
+            .expect("failed to populate double[] for RAPL result");
+        array
+    }
 }
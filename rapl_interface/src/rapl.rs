@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Root of the Linux powercap sysfs tree that exposes RAPL energy counters.
+const POWERCAP_ROOT: &str = "/sys/class/powercap";
+
+/// RAPL domains we report, in the order callers receive their joule deltas.
+pub const DOMAIN_NAMES: [&str; 5] = ["package", "core", "uncore", "dram", "psys"];
+
+/// Number of domains in [`DOMAIN_NAMES`].
+pub const DOMAIN_COUNT: usize = DOMAIN_NAMES.len();
+
+/// Energy consumed per RAPL domain between a `start_rapl()`/`stop_rapl()`
+/// pair, in joules. Domains the platform doesn't expose report `0.0`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnergyDeltas {
+    pub package: f64,
+    pub core: f64,
+    pub uncore: f64,
+    pub dram: f64,
+    pub psys: f64,
+}
+
+impl EnergyDeltas {
+    /// Flattened in [`DOMAIN_NAMES`] order, for callers (the C and JNI
+    /// bindings) that hand the result back as a plain array.
+    pub fn to_array(self) -> [f64; DOMAIN_COUNT] {
+        [self.package, self.core, self.uncore, self.dram, self.psys]
+    }
+}
+
+struct Domain {
+    energy_path: PathBuf,
+    max_range_uj: u64,
+}
+
+struct RaplState {
+    domains: [Option<Domain>; DOMAIN_COUNT],
+    start_uj: [Option<u64>; DOMAIN_COUNT],
+}
+
+static STATE: Mutex<Option<RaplState>> = Mutex::new(None);
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Find the powercap zone whose `name` file matches `domain`, searching
+/// top-level zones (`package-*`, `psys`) and their immediate children
+/// (`core`, `uncore`, `dram`), since the zone index is platform-dependent.
+fn find_domain(domain: &str) -> Option<Domain> {
+    let root = fs::read_dir(POWERCAP_ROOT).ok()?;
+    for entry in root.flatten() {
+        let dir = entry.path();
+        if domain_matches(&dir, domain) {
+            if let Some(d) = domain_at(&dir) {
+                return Some(d);
+            }
+        }
+        let Ok(children) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for child in children.flatten() {
+            let child_dir = child.path();
+            if domain_matches(&child_dir, domain) {
+                if let Some(d) = domain_at(&child_dir) {
+                    return Some(d);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn domain_matches(dir: &Path, domain: &str) -> bool {
+    let name = fs::read_to_string(dir.join("name")).unwrap_or_default();
+    let name = name.trim();
+    match domain {
+        "package" => name.starts_with("package"),
+        _ => name == domain,
+    }
+}
+
+fn domain_at(dir: &Path) -> Option<Domain> {
+    let energy_path = dir.join("energy_uj");
+    if !energy_path.exists() {
+        return None;
+    }
+    let max_range_uj = read_u64(&dir.join("max_energy_range_uj")).unwrap_or(u64::MAX);
+    Some(Domain {
+        energy_path,
+        max_range_uj,
+    })
+}
+
+/// Snapshot every available RAPL domain and stash the result for the
+/// matching `stop_rapl()` call. Returns `0` on success, `-1` if no RAPL
+/// domain could be found on this platform.
+pub fn start_rapl() -> i32 {
+    let domains = DOMAIN_NAMES.map(find_domain);
+    if domains.iter().all(Option::is_none) {
+        return -1;
+    }
+    let start_uj = domains
+        .each_ref()
+        .map(|d| d.as_ref().and_then(|d| read_u64(&d.energy_path)));
+    *STATE.lock().unwrap() = Some(RaplState { domains, start_uj });
+    0
+}
+
+/// Read the current counters and compute the joules consumed since the
+/// last `start_rapl()`, per domain.
+pub fn stop_rapl() -> EnergyDeltas {
+    let Some(state) = STATE.lock().unwrap().take() else {
+        return EnergyDeltas::default();
+    };
+
+    let mut deltas = EnergyDeltas::default();
+    let fields: [&mut f64; DOMAIN_COUNT] = [
+        &mut deltas.package,
+        &mut deltas.core,
+        &mut deltas.uncore,
+        &mut deltas.dram,
+        &mut deltas.psys,
+    ];
+
+    for (i, field) in fields.into_iter().enumerate() {
+        let Some(domain) = &state.domains[i] else {
+            continue;
+        };
+        let Some(start) = state.start_uj[i] else {
+            continue;
+        };
+        let Some(end) = read_u64(&domain.energy_path) else {
+            continue;
+        };
+        let delta_uj = if end >= start {
+            end - start
+        } else {
+            // The counter wrapped around `max_energy_range_uj` during the run.
+            domain.max_range_uj.saturating_sub(start) + end
+        };
+        *field = delta_uj as f64 / 1_000_000.0;
+    }
+
+    deltas
+}